@@ -0,0 +1,256 @@
+use anyhow::Context;
+use bluest::{Adapter, Device, Uuid};
+use std::time::Duration;
+use tokio::time::interval;
+
+/// Govee's BLE GATT service/characteristic used for issuing control packets.
+/// Every packet written here is 20 bytes: a command byte, a sub-command
+/// byte, a zero-padded payload and a trailing XOR checksum.
+const CONTROL_SERVICE: Uuid = Uuid::from_u128(0x000102030405060708090a0b0c0d1910);
+const CONTROL_CHARACTERISTIC: Uuid = Uuid::from_u128(0x000102030405060708090a0b0c0d2b11);
+
+const CMD_SET: u8 = 0x33;
+const CMD_KEEPALIVE: u8 = 0xaa;
+
+const OP_POWER: u8 = 0x01;
+const OP_BRIGHTNESS: u8 = 0x04;
+const OP_COLOR: u8 = 0x05;
+const OP_COLOR_MANUAL: u8 = 0x02;
+/// Sub-op of `OP_COLOR` used for `Temperature` requests. The BLE
+/// characteristic has no dedicated "kelvin" field, so this still carries
+/// an RGB approximation of the requested color (via `kelvin_to_rgb`)
+/// rather than driving a separate white-LED channel; using a distinct
+/// sub-op from `OP_COLOR_MANUAL` just keeps `Temperature` and `Color`
+/// from colliding on the wire, mirroring the cloud API's split between
+/// `colorTemperatureK` and `colorRgb`.
+const OP_COLOR_TEMPERATURE: u8 = 0x01;
+
+/// How often we must refresh the keep-alive packet to hold the connection
+/// open; Govee devices drop the link if they don't see one of these for a
+/// few seconds.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Builds one of the fixed 20-byte command packets that the Govee BLE
+/// protocol expects: `[cmd, op, ...payload (zero padded), checksum]`.
+fn build_packet(cmd: u8, op: u8, payload: &[u8]) -> [u8; 20] {
+    let mut packet = [0u8; 20];
+    packet[0] = cmd;
+    packet[1] = op;
+    packet[2..2 + payload.len()].copy_from_slice(payload);
+
+    let checksum = packet[0..19].iter().fold(0u8, |acc, b| acc ^ b);
+    packet[19] = checksum;
+    packet
+}
+
+fn keepalive_packet() -> [u8; 20] {
+    build_packet(CMD_KEEPALIVE, 0x01, &[])
+}
+
+/// Very rough black-body approximation used to turn a requested Kelvin
+/// value into the RGB white-point payload the color packet expects, since
+/// the BLE protocol has no dedicated "kelvin" field.
+fn kelvin_to_rgb(kelvin: u32) -> (u8, u8, u8) {
+    let temp = (kelvin.clamp(1000, 40000) as f64) / 100.0;
+
+    let red = if temp <= 66.0 {
+        255.0
+    } else {
+        (329.698_727_46 * (temp - 60.0).powf(-0.133_204_759_2)).clamp(0.0, 255.0)
+    };
+
+    let green = if temp <= 66.0 {
+        (99.470_802_59 * temp.ln() - 161.119_568_17).clamp(0.0, 255.0)
+    } else {
+        (288.122_169_53 * (temp - 60.0).powf(-0.075_514_849_2)).clamp(0.0, 255.0)
+    };
+
+    let blue = if temp >= 66.0 {
+        255.0
+    } else if temp <= 19.0 {
+        0.0
+    } else {
+        (138.517_731_92 * (temp - 10.0).ln() - 305.044_792_73).clamp(0.0, 255.0)
+    };
+
+    (red as u8, green as u8, blue as u8)
+}
+
+/// A short-lived connection to a single Govee device over BLE, used to
+/// issue the same handful of commands that `HttpControlCommand` issues
+/// over the cloud API.
+pub struct BleDevice {
+    device: Device,
+    characteristic: bluest::Characteristic,
+}
+
+impl BleDevice {
+    /// Connects to the device whose advertised name or BLE address matches
+    /// `id`. Checks the adapter's already-connected devices first, then
+    /// scans for up to 10 seconds.
+    pub async fn connect(id: &str) -> anyhow::Result<Self> {
+        let adapter = Adapter::default()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("no bluetooth adapter available"))?;
+        adapter.wait_available().await?;
+
+        let device = Self::find_device(&adapter, id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("no BLE device found matching '{id}'"))?;
+
+        adapter
+            .connect_device(&device)
+            .await
+            .with_context(|| format!("connecting to BLE device '{id}'"))?;
+
+        // Discovered once up front and cached: re-running GATT discovery
+        // on every write would mean the keep-alive loop re-walks the
+        // whole service/characteristic table every 2 seconds.
+        let characteristic = Self::discover_characteristic(&device).await?;
+
+        Ok(Self {
+            device,
+            characteristic,
+        })
+    }
+
+    async fn find_device(adapter: &Adapter, id: &str) -> anyhow::Result<Option<Device>> {
+        use futures_util::StreamExt;
+
+        if let Ok(devices) = adapter.connected_devices().await {
+            for device in devices {
+                if Self::matches(&device, id) {
+                    return Ok(Some(device));
+                }
+            }
+        }
+
+        let mut scan = adapter.scan(&[CONTROL_SERVICE]).await?;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while let Ok(Some(discovered)) =
+            tokio::time::timeout_at(deadline, scan.next()).await
+        {
+            if Self::matches(&discovered.device, id) {
+                return Ok(Some(discovered.device));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn matches(device: &Device, id: &str) -> bool {
+        device.name().map(|n| n == id).unwrap_or(false) || device.id().to_string() == id
+    }
+
+    async fn discover_characteristic(device: &Device) -> anyhow::Result<bluest::Characteristic> {
+        for service in device.discover_services().await? {
+            if service.uuid() == CONTROL_SERVICE {
+                for characteristic in service.discover_characteristics().await? {
+                    if characteristic.uuid() == CONTROL_CHARACTERISTIC {
+                        return Ok(characteristic);
+                    }
+                }
+            }
+        }
+        anyhow::bail!("device does not expose the Govee control characteristic")
+    }
+
+    async fn write(&self, packet: &[u8; 20]) -> anyhow::Result<()> {
+        self.characteristic.write_without_response(packet).await?;
+        Ok(())
+    }
+
+    /// Writes `packet` and then keeps the connection alive for long enough
+    /// for the device to apply it, by issuing keep-alive packets on
+    /// `KEEPALIVE_INTERVAL` until `hold_for` has elapsed.
+    async fn write_and_hold(&self, packet: &[u8; 20], hold_for: Duration) -> anyhow::Result<()> {
+        self.write(packet).await?;
+
+        let mut ticker = interval(KEEPALIVE_INTERVAL);
+        let deadline = tokio::time::Instant::now() + hold_for;
+        loop {
+            ticker.tick().await;
+            if tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            self.write(&keepalive_packet()).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn power(&self, on: bool) -> anyhow::Result<()> {
+        let packet = build_packet(CMD_SET, OP_POWER, &[if on { 0x01 } else { 0x00 }]);
+        self.write_and_hold(&packet, KEEPALIVE_INTERVAL).await
+    }
+
+    /// `percent` is clamped to 0..=100, matching the CLI's existing
+    /// `Brightness { percent: u8 }` argument, and scaled up to the 0..=255
+    /// range the BLE packet expects.
+    pub async fn brightness(&self, percent: u8) -> anyhow::Result<()> {
+        let percent = percent.min(100);
+        let value = ((percent as u32) * 255 / 100) as u8;
+        let packet = build_packet(CMD_SET, OP_BRIGHTNESS, &[value]);
+        self.write_and_hold(&packet, KEEPALIVE_INTERVAL).await
+    }
+
+    pub async fn color_rgb(&self, r: u8, g: u8, b: u8) -> anyhow::Result<()> {
+        let packet = build_packet(CMD_SET, OP_COLOR, &[OP_COLOR_MANUAL, r, g, b]);
+        self.write_and_hold(&packet, KEEPALIVE_INTERVAL).await
+    }
+
+    /// Like `color_rgb`, this is still an RGB approximation of the
+    /// requested Kelvin value (via `kelvin_to_rgb`) rather than a true
+    /// white-point command — the BLE protocol has no raw "kelvin" field.
+    /// It's sent under the distinct `OP_COLOR_TEMPERATURE` sub-op rather
+    /// than `OP_COLOR_MANUAL` so it doesn't collide on the wire with a
+    /// `color_rgb` call, matching the cloud API's split between
+    /// `colorTemperatureK` and `colorRgb`.
+    pub async fn color_temperature(&self, kelvin: u32) -> anyhow::Result<()> {
+        let (r, g, b) = kelvin_to_rgb(kelvin);
+        let packet = build_packet(CMD_SET, OP_COLOR, &[OP_COLOR_TEMPERATURE, r, g, b]);
+        self.write_and_hold(&packet, KEEPALIVE_INTERVAL).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packet_checksum_is_xor_of_preceding_bytes() {
+        let packet = build_packet(CMD_SET, OP_BRIGHTNESS, &[0x80]);
+        let expected = packet[0..19].iter().fold(0u8, |acc, b| acc ^ b);
+        assert_eq!(packet[19], expected);
+        assert_eq!(packet[0], CMD_SET);
+        assert_eq!(packet[1], OP_BRIGHTNESS);
+        assert_eq!(packet[2], 0x80);
+    }
+
+    #[test]
+    fn keepalive_packet_has_stable_checksum() {
+        let packet = keepalive_packet();
+        let expected = packet[0..19].iter().fold(0u8, |acc, b| acc ^ b);
+        assert_eq!(packet[19], expected);
+        assert_eq!(packet[0], CMD_KEEPALIVE);
+    }
+
+    #[test]
+    fn kelvin_to_rgb_is_warm_below_neutral() {
+        let (r, g, b) = kelvin_to_rgb(2700);
+        assert_eq!(r, 255);
+        assert!(b < r, "2700K should skew warm (less blue than red)");
+    }
+
+    #[test]
+    fn kelvin_to_rgb_is_neutral_white_near_6600() {
+        let (r, g, b) = kelvin_to_rgb(6600);
+        assert!(r > 240 && g > 240 && b > 240);
+    }
+
+    #[test]
+    fn kelvin_to_rgb_clamps_out_of_range_input() {
+        // Should not panic on ln()/powf() of out-of-domain values.
+        let _ = kelvin_to_rgb(0);
+        let _ = kelvin_to_rgb(u32::MAX);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::http_api::{DeviceParameters, IntegerRange};
+use futures_util::future::join_all;
+
+/// Fans a single action out to every device in a configured group, e.g.
+/// `govee group-control --group "Living Room" brightness 30`. Capability
+/// differences between members are reconciled rather than treated as
+/// errors: a member missing `colorTemperatureK` is skipped, and
+/// brightness is clamped against each member's own `IntegerRange`.
+///
+/// Depends on `Config::resolve_device_group`, which isn't added by this
+/// change, and isn't yet registered as a top-level `govee` subcommand;
+/// both of those live in files outside this snapshot.
+#[derive(clap::Parser, Debug)]
+pub struct GroupControlCommand {
+    /// Name of a previously configured device group
+    #[arg(long)]
+    pub group: String,
+
+    #[command(subcommand)]
+    cmd: GroupSubCommand,
+}
+
+/// `pub(crate)` so `hass_mqtt::group`'s command dispatch can apply the
+/// exact same reconciliation logic to commands arriving from Home
+/// Assistant as this CLI applies to commands typed by hand.
+#[derive(clap::Parser, Clone, Debug)]
+pub(crate) enum GroupSubCommand {
+    On,
+    Off,
+    Brightness { percent: u8 },
+    Temperature { kelvin: u32 },
+    Color { color: csscolorparser::Color },
+    Scene { scene: String },
+}
+
+impl GroupControlCommand {
+    pub async fn run(&self, args: &crate::Args) -> anyhow::Result<()> {
+        let client = args.api_args.api_client()?;
+        let group = args
+            .config
+            .resolve_device_group(&self.group)
+            .ok_or_else(|| anyhow::anyhow!("no group named '{}'", self.group))?;
+
+        anyhow::ensure!(!group.members.is_empty(), "group '{}' has no members", self.group);
+
+        let results = fan_out(&client, &group.members, &self.cmd).await;
+
+        let mut had_error = false;
+        for (device_id, result) in results {
+            match result {
+                Ok(true) => println!("{device_id}: ok"),
+                Ok(false) => println!("{device_id}: skipped (missing capability)"),
+                Err(err) => {
+                    had_error = true;
+                    eprintln!("{device_id}: {err:#}");
+                }
+            }
+        }
+
+        anyhow::ensure!(!had_error, "one or more group members failed");
+        Ok(())
+    }
+
+    /// Applies `cmd` to a single member, returning `Ok(false)` when the
+    /// member lacks the relevant capability so the caller can report it
+    /// as skipped rather than failed.
+    pub(crate) async fn apply_to_member(
+        client: &crate::http_api::GoveeApiClient,
+        device_id: &str,
+        cmd: &GroupSubCommand,
+    ) -> anyhow::Result<bool> {
+        let device = client.get_device_by_id(device_id).await?;
+
+        match cmd {
+            GroupSubCommand::On | GroupSubCommand::Off => {
+                let Some(cap) = device.capability_by_instance("powerSwitch") else {
+                    return Ok(false);
+                };
+                let value = cap
+                    .enum_parameter_by_name(match cmd {
+                        GroupSubCommand::On => "on",
+                        GroupSubCommand::Off => "off",
+                        _ => unreachable!(),
+                    })
+                    .ok_or_else(|| anyhow::anyhow!("powerSwitch has no on/off!?"))?;
+                client.control_device(&device, &cap, value).await?;
+            }
+
+            GroupSubCommand::Brightness { percent } => {
+                let Some(cap) = device.capability_by_instance("brightness") else {
+                    return Ok(false);
+                };
+                let value = match &cap.parameters {
+                    DeviceParameters::Integer {
+                        range: IntegerRange { min, max, .. },
+                        ..
+                    } => (*percent as u32).max(*min).min(*max),
+                    _ => anyhow::bail!("unexpected parameter type for brightness"),
+                };
+                client.control_device(&device, &cap, value).await?;
+            }
+
+            GroupSubCommand::Temperature { kelvin } => {
+                // Not every member of a group supports color temperature
+                // (some may be RGB-only); skip rather than fail those.
+                let Some(cap) = device.capability_by_instance("colorTemperatureK") else {
+                    return Ok(false);
+                };
+                let value = match &cap.parameters {
+                    DeviceParameters::Integer {
+                        range: IntegerRange { min, max, .. },
+                        ..
+                    } => (*kelvin).max(*min).min(*max),
+                    _ => anyhow::bail!("unexpected parameter type for colorTemperatureK"),
+                };
+                client.control_device(&device, &cap, value).await?;
+            }
+
+            GroupSubCommand::Color { color } => {
+                let Some(cap) = device.capability_by_instance("colorRgb") else {
+                    return Ok(false);
+                };
+                let [r, g, b, _a] = color.to_rgba8();
+                let value = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+                client.control_device(&device, &cap, value).await?;
+            }
+
+            GroupSubCommand::Scene { scene } => {
+                let scene_caps = client.get_device_scenes(&device).await?;
+                for cap in scene_caps {
+                    let DeviceParameters::Enum { options } = &cap.parameters else {
+                        anyhow::bail!("unexpected type {cap:#?}");
+                    };
+                    for opt in options {
+                        if scene.eq_ignore_ascii_case(&opt.name) {
+                            client.control_device(&device, &cap, opt.value.clone()).await?;
+                            return Ok(true);
+                        }
+                    }
+                }
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Applies `cmd` to every member of `members` concurrently. Shared by the
+/// CLI (`GroupControlCommand::run`) and the Home Assistant command
+/// dispatch in `crate::hass_mqtt::group`, so a command typed on the CLI
+/// and one arriving from a HASS group entity are reconciled identically.
+pub(crate) async fn fan_out(
+    client: &crate::http_api::GoveeApiClient,
+    members: &[String],
+    cmd: &GroupSubCommand,
+) -> Vec<(String, anyhow::Result<bool>)> {
+    join_all(members.iter().map(|device_id| {
+        let client = client.clone();
+        let cmd = cmd.clone();
+        async move {
+            (
+                device_id.clone(),
+                GroupControlCommand::apply_to_member(&client, device_id, &cmd).await,
+            )
+        }
+    }))
+    .await
+}
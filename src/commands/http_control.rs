@@ -1,14 +1,30 @@
+use crate::ble_control::BleDevice;
 use crate::http_api::{DeviceParameters, EnumOption, IntegerRange};
+use anyhow::Context;
 
 #[derive(clap::Parser, Debug)]
 pub struct HttpControlCommand {
+    /// The device id (cloud/LAN transports) or BLE name/address
+    /// (`--transport ble`) to control.
     #[arg(long)]
     pub id: String,
 
+    /// How to reach the device. `cloud` goes via the Govee HTTP API;
+    /// `ble` talks directly to a nearby device and doesn't require
+    /// cloud or LAN connectivity.
+    #[arg(long, value_enum, default_value_t = Transport::Cloud)]
+    pub transport: Transport,
+
     #[command(subcommand)]
     cmd: SubCommand,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transport {
+    Cloud,
+    Ble,
+}
+
 #[derive(clap::Parser, Debug)]
 enum SubCommand {
     On,
@@ -31,6 +47,21 @@ enum SubCommand {
         #[arg(required_unless_present = "list")]
         scene: Option<String>,
     },
+    Segment {
+        /// List how many addressable segments the device exposes, then exit
+        #[arg(long = "list-segments")]
+        list_segments: bool,
+
+        /// Comma-separated segment indices to target, e.g. `0,1,2`. May be
+        /// passed multiple times; each occurrence pairs positionally with
+        /// the `--color` at the same position.
+        #[arg(long = "segment")]
+        segments: Vec<String>,
+
+        /// Color to apply to the `--segment` at the same position
+        #[arg(long = "color")]
+        colors: Vec<csscolorparser::Color>,
+    },
     Music {
         /// List available modes
         #[arg(long)]
@@ -53,6 +84,10 @@ enum SubCommand {
 
 impl HttpControlCommand {
     pub async fn run(&self, args: &crate::Args) -> anyhow::Result<()> {
+        if self.transport == Transport::Ble {
+            return self.run_ble().await;
+        }
+
         let client = args.api_args.api_client()?;
         let device = client.get_device_by_id(&self.id).await?;
 
@@ -116,6 +151,42 @@ impl HttpControlCommand {
                 println!("{result:#?}");
             }
 
+            SubCommand::Segment {
+                list_segments,
+                segments,
+                colors,
+            } => {
+                let cap = device
+                    .capability_by_instance("segmentedColorRgb")
+                    .ok_or_else(|| anyhow::anyhow!("device has no segmentedColorRgb"))?;
+                let segment_count = Self::segment_count(&cap.parameters)?;
+
+                if *list_segments {
+                    println!("device exposes {segment_count} segment(s): 0..{}", segment_count - 1);
+                    return Ok(());
+                }
+
+                anyhow::ensure!(
+                    !segments.is_empty(),
+                    "specify at least one --segment/--color pair"
+                );
+                anyhow::ensure!(
+                    segments.len() == colors.len(),
+                    "each --segment must be paired with a --color"
+                );
+
+                let mut writes = vec![];
+                for (segment_list, color) in segments.iter().zip(colors.iter()) {
+                    let indices = Self::parse_segment_indices(segment_list, segment_count)?;
+                    writes.push(Self::segment_write_payload(&indices, color));
+                }
+
+                let result = client
+                    .control_device(&device, &cap, serde_json::Value::Array(writes))
+                    .await?;
+                println!("{result:#?}");
+            }
+
             SubCommand::Scene { list, scene } => {
                 let scene_caps = client.get_device_scenes(&device).await?;
 
@@ -227,4 +298,118 @@ impl HttpControlCommand {
 
         Ok(())
     }
+
+    /// Handles the subset of subcommands that make sense without a cloud
+    /// connection: `On`/`Off`/`Brightness`/`Temperature`/`Color`. `Scene`
+    /// and `Music` depend on cloud-provided capability data and aren't
+    /// supported over BLE.
+    async fn run_ble(&self) -> anyhow::Result<()> {
+        let device = BleDevice::connect(&self.id).await?;
+
+        match &self.cmd {
+            SubCommand::On => device.power(true).await?,
+            SubCommand::Off => device.power(false).await?,
+            SubCommand::Brightness { percent } => device.brightness(*percent).await?,
+            SubCommand::Temperature { kelvin } => device.color_temperature(*kelvin).await?,
+            SubCommand::Color { color } => {
+                let [r, g, b, _a] = color.to_rgba8();
+                device.color_rgb(r, g, b).await?;
+            }
+            SubCommand::Scene { .. } | SubCommand::Music { .. } | SubCommand::Segment { .. } => {
+                anyhow::bail!("--transport ble does not support this subcommand")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Looks up how many addressable segments a `segmentedColorRgb`
+    /// capability supports, by finding its `segment` field's integer
+    /// range. The field's `max` is the highest valid index, so the count
+    /// is one more than that.
+    fn segment_count(parameters: &DeviceParameters) -> anyhow::Result<u32> {
+        match parameters {
+            DeviceParameters::Struct { fields } => {
+                for f in fields {
+                    if f.field_name == "segment" {
+                        if let DeviceParameters::Integer {
+                            range: IntegerRange { max, .. },
+                            ..
+                        } = &f.field_type
+                        {
+                            return Ok(*max as u32 + 1);
+                        }
+                    }
+                }
+                anyhow::bail!("segment field not found in {parameters:#?}");
+            }
+            _ => anyhow::bail!("unexpected type {parameters:#?}"),
+        }
+    }
+
+    /// Builds one element of the array-valued payload `segmentedColorRgb`
+    /// expects: `{"segment": [indices...], "rgb": <packed 0xRRGGBB>}`,
+    /// matching the same `segment`/`rgb` field names the capability's own
+    /// `DeviceParameters::Struct` fields use.
+    fn segment_write_payload(indices: &[u32], color: &csscolorparser::Color) -> serde_json::Value {
+        let [r, g, b, _a] = color.to_rgba8();
+        let rgb = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+        serde_json::json!({ "segment": indices, "rgb": rgb })
+    }
+
+    /// Parses a comma-separated `--segment` value like `0,1,2` and
+    /// validates every index against the device's actual segment count.
+    fn parse_segment_indices(raw: &str, segment_count: u32) -> anyhow::Result<Vec<u32>> {
+        raw.split(',')
+            .map(|s| {
+                let idx: u32 = s
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("invalid segment index '{s}'"))?;
+                anyhow::ensure!(
+                    idx < segment_count,
+                    "segment index {idx} is out of range (device has {segment_count} segment(s))"
+                );
+                Ok(idx)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HttpControlCommand;
+
+    #[test]
+    fn parse_segment_indices_accepts_in_range() {
+        let indices = HttpControlCommand::parse_segment_indices("0,1,2", 4).unwrap();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn parse_segment_indices_rejects_out_of_range() {
+        let err = HttpControlCommand::parse_segment_indices("0,4", 4).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn parse_segment_indices_rejects_garbage() {
+        assert!(HttpControlCommand::parse_segment_indices("0,nope", 4).is_err());
+    }
+
+    #[test]
+    fn parse_segment_indices_trims_whitespace() {
+        let indices = HttpControlCommand::parse_segment_indices(" 0 , 1 ", 4).unwrap();
+        assert_eq!(indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn segment_write_payload_matches_expected_shape() {
+        let color: csscolorparser::Color = "#102030".parse().unwrap();
+        let payload = HttpControlCommand::segment_write_payload(&[0, 2], &color);
+        assert_eq!(
+            payload,
+            serde_json::json!({ "segment": [0, 2], "rgb": 0x102030u32 })
+        );
+    }
 }
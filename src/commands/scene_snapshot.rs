@@ -0,0 +1,69 @@
+use crate::scene_store::{LocalScene, SceneStore};
+
+/// Captures and restores local scenes: snapshots of the live
+/// power/brightness/color/color-temperature state of one or more devices,
+/// for scenes the Govee cloud doesn't offer as presets.
+///
+/// Not yet registered as a top-level `govee` subcommand; that dispatch
+/// lives in the top-level CLI enum, outside this file. `LocalScene::capture`
+/// also depends on `GoveeApiClient::get_device_state` returning a value
+/// with `value_for(instance)` - neither is added by this change, so this
+/// can't be exercised against a real client yet.
+#[derive(clap::Parser, Debug)]
+pub struct SceneSnapshotCommand {
+    #[command(subcommand)]
+    cmd: SubCommand,
+}
+
+#[derive(clap::Parser, Debug)]
+enum SubCommand {
+    /// Capture the current state of one or more devices into a named scene
+    Save {
+        name: String,
+
+        /// A device id to include in the scene; may be repeated
+        #[arg(long = "device", required = true)]
+        devices: Vec<String>,
+    },
+    /// Replay a previously captured scene
+    Restore { name: String },
+    /// List saved scenes
+    List,
+}
+
+fn store() -> anyhow::Result<SceneStore> {
+    let dirs = directories::ProjectDirs::from("", "", "govee2mqtt")
+        .ok_or_else(|| anyhow::anyhow!("unable to determine config directory"))?;
+    Ok(SceneStore::new(dirs.data_dir().join("scenes.json")))
+}
+
+impl SceneSnapshotCommand {
+    pub async fn run(&self, args: &crate::Args) -> anyhow::Result<()> {
+        let client = args.api_args.api_client()?;
+        let store = store()?;
+
+        match &self.cmd {
+            SubCommand::Save { name, devices } => {
+                let scene = LocalScene::capture(name, &client, devices).await?;
+                store.upsert(scene)?;
+                println!("saved scene '{name}' with {} device(s)", devices.len());
+            }
+
+            SubCommand::Restore { name } => {
+                let scene = store
+                    .get(name)?
+                    .ok_or_else(|| anyhow::anyhow!("no local scene named '{name}'"))?;
+                scene.restore(&client).await?;
+                println!("restored scene '{name}'");
+            }
+
+            SubCommand::List => {
+                for scene in store.load_all()? {
+                    println!("{} ({} device(s))", scene.name, scene.captures.len());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::http_api::DeviceParameters;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::time::interval;
+
+/// Streams a rapid series of RGB colors to a single device's `colorRgb`
+/// capability, smoothing between them so the output doesn't flicker and
+/// so we don't spam the cloud API faster than it can take writes.
+///
+/// Colors are read one per line as `r,g,b` (0-255 each) from stdin. This
+/// is intended to sit behind an external screen-sync or audio-visualizer
+/// process that pipes colors in as fast as it likes; frames that arrive
+/// faster than `--fps` are coalesced, keeping only the latest target.
+///
+/// Only the stdin input is implemented; UDP/TCP/DreamView inputs are a
+/// follow-up. Registering this as a top-level `govee` subcommand is also
+/// still pending - that dispatch lives in the top-level CLI enum, which
+/// isn't part of this file.
+#[derive(clap::Parser, Debug)]
+pub struct StreamColorCommand {
+    #[arg(long)]
+    pub id: String,
+
+    /// How many times per second to emit a color to the device.
+    #[arg(long, default_value_t = 30)]
+    pub fps: u32,
+
+    /// Exponential moving average smoothing factor in (0.0, 1.0]; smaller
+    /// values smooth more aggressively, 1.0 disables smoothing entirely.
+    /// Must be greater than 0.0, or colors would never converge.
+    #[arg(long, default_value_t = 0.35)]
+    pub alpha: f32,
+
+    /// Gamma applied per-channel before each color is emitted, to
+    /// compensate for LED strips whose perceived brightness isn't linear
+    /// in the raw RGB value.
+    #[arg(long, default_value_t = 2.2)]
+    pub gamma: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Rgb {
+    r: f32,
+    g: f32,
+    b: f32,
+}
+
+impl Rgb {
+    fn lerp_toward(self, target: Rgb, alpha: f32) -> Rgb {
+        Rgb {
+            r: self.r + alpha * (target.r - self.r),
+            g: self.g + alpha * (target.g - self.g),
+            b: self.b + alpha * (target.b - self.b),
+        }
+    }
+
+    fn gamma_corrected(self, gamma: f32) -> (u8, u8, u8) {
+        let apply = |c: f32| ((c / 255.0).max(0.0).powf(gamma) * 255.0).round().clamp(0.0, 255.0) as u8;
+        (apply(self.r), apply(self.g), apply(self.b))
+    }
+
+    fn to_device_value(self, gamma: f32) -> u32 {
+        let (r, g, b) = self.gamma_corrected(gamma);
+        ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+    }
+}
+
+fn parse_line(line: &str) -> Option<Rgb> {
+    let mut parts = line.trim().splitn(3, ',');
+    let r: f32 = parts.next()?.trim().parse().ok()?;
+    let g: f32 = parts.next()?.trim().parse().ok()?;
+    let b: f32 = parts.next()?.trim().parse().ok()?;
+    Some(Rgb { r, g, b })
+}
+
+impl StreamColorCommand {
+    pub async fn run(&self, args: &crate::Args) -> anyhow::Result<()> {
+        // `alpha == 0.0` would mean `out` never moves toward any target,
+        // so nothing meaningful would ever be sent; require > 0.0 rather
+        // than just non-negative.
+        anyhow::ensure!(
+            self.alpha > 0.0 && self.alpha <= 1.0,
+            "--alpha must be greater than 0.0 and at most 1.0"
+        );
+        anyhow::ensure!(self.fps > 0, "--fps must be greater than zero");
+
+        let client = args.api_args.api_client()?;
+        let device = client.get_device_by_id(&self.id).await?;
+        let cap = device
+            .capability_by_instance("colorRgb")
+            .ok_or_else(|| anyhow::anyhow!("device has no colorRgb"))?;
+
+        let (target_tx, mut target_rx) = tokio::sync::watch::channel(None::<Rgb>);
+
+        let reader = tokio::spawn(async move {
+            let stdin = tokio::io::stdin();
+            let mut lines = BufReader::new(stdin).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(rgb) = parse_line(&line) {
+                    // Coalesce: only the most recently parsed color
+                    // matters, so a plain `watch` channel (rather than an
+                    // mpsc queue) naturally drops anything we don't get
+                    // to in time.
+                    let _ = target_tx.send(Some(rgb));
+                }
+            }
+        });
+
+        let mut out = Rgb::default();
+        let mut target = None;
+        let mut last_sent_value = None;
+        let mut ticker = interval(Duration::from_secs_f64(1.0 / self.fps as f64));
+
+        // Collected into `outcome` instead of returning directly so that
+        // every exit path - including a `control_device` error - falls
+        // through to `reader.abort()` below, rather than leaking the
+        // stdin-reading task.
+        let outcome: anyhow::Result<()> = loop {
+            ticker.tick().await;
+
+            if let Some(new_target) = *target_rx.borrow_and_update() {
+                target = Some(new_target);
+            }
+
+            let Some(current_target) = target else {
+                if reader.is_finished() {
+                    break Ok(());
+                }
+                continue;
+            };
+
+            out = out.lerp_toward(current_target, self.alpha);
+            let value = out.to_device_value(self.gamma);
+
+            if Some(value) != last_sent_value {
+                let sent = match &cap.parameters {
+                    DeviceParameters::Struct { .. } | DeviceParameters::Integer { .. } => {
+                        client.control_device(&device, &cap, value).await
+                    }
+                    _ => Err(anyhow::anyhow!("unexpected parameter type for colorRgb")),
+                };
+
+                match sent {
+                    Ok(_) => last_sent_value = Some(value),
+                    Err(err) => break Err(err),
+                }
+            }
+
+            if reader.is_finished() {
+                break Ok(());
+            }
+        };
+
+        reader.abort();
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_line_accepts_well_formed_triples() {
+        let rgb = parse_line("255,128,0").unwrap();
+        assert_eq!(rgb, Rgb { r: 255.0, g: 128.0, b: 0.0 });
+    }
+
+    #[test]
+    fn parse_line_trims_whitespace() {
+        let rgb = parse_line(" 10 , 20 , 30 \n").unwrap();
+        assert_eq!(rgb, Rgb { r: 10.0, g: 20.0, b: 30.0 });
+    }
+
+    #[test]
+    fn parse_line_rejects_malformed_input() {
+        assert!(parse_line("not a color").is_none());
+        assert!(parse_line("1,2").is_none());
+    }
+
+    #[test]
+    fn lerp_toward_moves_partway_to_target() {
+        let out = Rgb::default().lerp_toward(Rgb { r: 100.0, g: 0.0, b: 0.0 }, 0.5);
+        assert_eq!(out, Rgb { r: 50.0, g: 0.0, b: 0.0 });
+    }
+
+    #[test]
+    fn lerp_toward_alpha_one_jumps_immediately() {
+        let target = Rgb { r: 10.0, g: 20.0, b: 30.0 };
+        let out = Rgb::default().lerp_toward(target, 1.0);
+        assert_eq!(out, target);
+    }
+
+    #[test]
+    fn gamma_corrected_round_trips_extremes() {
+        assert_eq!(Rgb { r: 0.0, g: 0.0, b: 0.0 }.gamma_corrected(2.2), (0, 0, 0));
+        assert_eq!(Rgb { r: 255.0, g: 255.0, b: 255.0 }.gamma_corrected(2.2), (255, 255, 255));
+    }
+
+    #[test]
+    fn gamma_corrected_darkens_midtones_for_gamma_above_one() {
+        let (r, _, _) = Rgb { r: 128.0, g: 128.0, b: 128.0 }.gamma_corrected(2.2);
+        assert!(r < 128, "gamma > 1 should darken midtones, got {r}");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::commands::group_control::{fan_out, GroupSubCommand};
+use crate::hass_mqtt::base::EntityConfig;
+use crate::hass_mqtt::instance::EntityInstance;
+use crate::http_api::GoveeApiClient;
+use crate::service::hass::HassClient;
+use crate::service::state::StateHandle;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// A named collection of Govee device ids that should be controlled
+/// together as a single unit, configured by the user rather than
+/// discovered from the Govee account.
+#[derive(Clone, Debug)]
+pub struct DeviceGroup {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+/// Publishes a `DeviceGroup` as a single Home Assistant light entity.
+/// Commands sent to it are fanned out to every member device; see
+/// `crate::commands::group_control` for that side of things. This entity
+/// carries no state of its own: each member republishes its own state
+/// independently via its regular per-device entity.
+///
+/// `publish` and `spawn_command_dispatch` both need to actually be called
+/// from the HASS discovery/startup path for any of this to take effect;
+/// that startup wiring lives outside this file.
+#[derive(Serialize, Clone, Debug)]
+pub struct GroupConfig {
+    #[serde(flatten)]
+    pub base: EntityConfig,
+
+    pub command_topic: String,
+    pub brightness_command_topic: String,
+    pub color_temp_command_topic: String,
+    pub rgb_command_topic: String,
+    pub payload_on: String,
+    pub payload_off: String,
+
+    /// Not part of the discovery payload: the member device ids that this
+    /// entity's command topics fan commands out to.
+    #[serde(skip)]
+    pub members: Vec<String>,
+}
+
+impl GroupConfig {
+    pub async fn publish(&self, state: &StateHandle, client: &HassClient) -> anyhow::Result<()> {
+        let disco = state.get_hass_disco_prefix().await;
+        let topic = format!(
+            "{disco}/light/{unique_id}/config",
+            unique_id = self.base.unique_id
+        );
+
+        client.publish_obj(topic, self).await
+    }
+
+    /// Subscribes to this entity's four command topics and fans each
+    /// incoming payload out to every group member via
+    /// `group_control::fan_out`, so flipping the HASS entity actually does
+    /// something instead of just appearing in the UI.
+    pub async fn spawn_command_dispatch(
+        &self,
+        hass_client: HassClient,
+        api_client: GoveeApiClient,
+    ) -> anyhow::Result<()> {
+        let topics = [
+            (self.command_topic.clone(), Self::decode_power as DecodeFn),
+            (self.brightness_command_topic.clone(), Self::decode_brightness),
+            (self.color_temp_command_topic.clone(), Self::decode_color_temp),
+            (self.rgb_command_topic.clone(), Self::decode_rgb),
+        ];
+
+        for (topic, decode) in topics {
+            let mut incoming = hass_client.subscribe(&topic).await?;
+            let members = self.members.clone();
+            let api_client = api_client.clone();
+            let topic_for_log = topic.clone();
+
+            tokio::spawn(async move {
+                while let Some(payload) = incoming.recv().await {
+                    let cmd = match decode(&payload) {
+                        Ok(cmd) => cmd,
+                        Err(err) => {
+                            log::warn!("ignoring group command on '{topic_for_log}': {err:#}");
+                            continue;
+                        }
+                    };
+
+                    for (device_id, result) in fan_out(&api_client, &members, &cmd).await {
+                        if let Err(err) = result {
+                            log::warn!("group member '{device_id}' failed to apply command: {err:#}");
+                        }
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    fn decode_power(payload: &[u8]) -> anyhow::Result<GroupSubCommand> {
+        match std::str::from_utf8(payload)?.trim() {
+            "ON" => Ok(GroupSubCommand::On),
+            "OFF" => Ok(GroupSubCommand::Off),
+            other => anyhow::bail!("unexpected power payload '{other}'"),
+        }
+    }
+
+    fn decode_brightness(payload: &[u8]) -> anyhow::Result<GroupSubCommand> {
+        let percent: u8 = std::str::from_utf8(payload)?.trim().parse()?;
+        Ok(GroupSubCommand::Brightness { percent })
+    }
+
+    fn decode_color_temp(payload: &[u8]) -> anyhow::Result<GroupSubCommand> {
+        let kelvin: u32 = std::str::from_utf8(payload)?.trim().parse()?;
+        Ok(GroupSubCommand::Temperature { kelvin })
+    }
+
+    fn decode_rgb(payload: &[u8]) -> anyhow::Result<GroupSubCommand> {
+        let text = std::str::from_utf8(payload)?.trim();
+        let mut parts = text.splitn(3, ',');
+        let r: u8 = parts.next().ok_or_else(|| anyhow::anyhow!("missing red channel"))?.trim().parse()?;
+        let g: u8 = parts.next().ok_or_else(|| anyhow::anyhow!("missing green channel"))?.trim().parse()?;
+        let b: u8 = parts.next().ok_or_else(|| anyhow::anyhow!("missing blue channel"))?.trim().parse()?;
+        let color = csscolorparser::Color::from_rgba8(r, g, b, 255);
+        Ok(GroupSubCommand::Color { color })
+    }
+}
+
+type DecodeFn = fn(&[u8]) -> anyhow::Result<GroupSubCommand>;
+
+#[async_trait]
+impl EntityInstance for GroupConfig {
+    async fn publish_config(&self, state: &StateHandle, client: &HassClient) -> anyhow::Result<()> {
+        self.publish(&state, &client).await
+    }
+
+    async fn notify_state(&self, _client: &HassClient) -> anyhow::Result<()> {
+        // A group has no state of its own; each member device's own
+        // entity is the source of truth.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_power_accepts_on_and_off() {
+        assert!(matches!(
+            GroupConfig::decode_power(b"ON").unwrap(),
+            GroupSubCommand::On
+        ));
+        assert!(matches!(
+            GroupConfig::decode_power(b"OFF").unwrap(),
+            GroupSubCommand::Off
+        ));
+    }
+
+    #[test]
+    fn decode_power_rejects_unknown_payload() {
+        assert!(GroupConfig::decode_power(b"TOGGLE").is_err());
+    }
+
+    #[test]
+    fn decode_brightness_parses_percent() {
+        let GroupSubCommand::Brightness { percent } = GroupConfig::decode_brightness(b"42").unwrap() else {
+            panic!("expected Brightness");
+        };
+        assert_eq!(percent, 42);
+    }
+
+    #[test]
+    fn decode_rgb_parses_comma_separated_channels() {
+        let GroupSubCommand::Color { color } = GroupConfig::decode_rgb(b"10,20,30").unwrap() else {
+            panic!("expected Color");
+        };
+        assert_eq!(color.to_rgba8(), [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn decode_rgb_rejects_missing_channels() {
+        assert!(GroupConfig::decode_rgb(b"10,20").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::hass_mqtt::base::EntityConfig;
 use crate::hass_mqtt::instance::EntityInstance;
+use crate::http_api::GoveeApiClient;
+use crate::scene_store::LocalScene;
 use crate::service::hass::HassClient;
 use crate::service::state::StateHandle;
 use async_trait::async_trait;
@@ -15,6 +17,23 @@ pub struct SceneConfig {
 }
 
 impl SceneConfig {
+    /// Builds the discovery config for a user-defined local scene (see
+    /// `crate::scene_store::LocalScene`), so activating it from Home
+    /// Assistant looks exactly like activating a Govee cloud scene. Pair
+    /// this with `spawn_local_scene_dispatch` so that activation actually
+    /// restores the scene.
+    ///
+    /// Neither `publish` nor `spawn_local_scene_dispatch` is called from
+    /// the HASS discovery/startup path yet for configs built this way;
+    /// that wiring lives outside this file.
+    pub fn for_local_scene(base: EntityConfig, command_topic: String) -> Self {
+        Self {
+            base,
+            command_topic,
+            payload_on: "ON".to_string(),
+        }
+    }
+
     pub async fn publish(&self, state: &StateHandle, client: &HassClient) -> anyhow::Result<()> {
         let disco = state.get_hass_disco_prefix().await;
         let topic = format!(
@@ -24,6 +43,42 @@ impl SceneConfig {
 
         client.publish_obj(topic, self).await
     }
+
+    /// Subscribes to this entity's `command_topic` and calls
+    /// `LocalScene::restore` whenever `payload_on` arrives, so activating
+    /// the entity from Home Assistant actually replays the captured
+    /// scene. Only meaningful for configs built via `for_local_scene`;
+    /// cloud scenes are dispatched elsewhere.
+    pub async fn spawn_local_scene_dispatch(
+        &self,
+        hass_client: HassClient,
+        api_client: GoveeApiClient,
+        scene: LocalScene,
+    ) -> anyhow::Result<()> {
+        let mut incoming = hass_client.subscribe(&self.command_topic).await?;
+        let payload_on = self.payload_on.clone();
+        let topic = self.command_topic.clone();
+
+        tokio::spawn(async move {
+            while let Some(payload) = incoming.recv().await {
+                let matches_on = std::str::from_utf8(&payload)
+                    .map(|text| text.trim() == payload_on)
+                    .unwrap_or(false);
+                if !matches_on {
+                    continue;
+                }
+
+                if let Err(err) = scene.restore(&api_client).await {
+                    log::error!(
+                        "failed to restore local scene '{}' from '{topic}': {err:#}",
+                        scene.name
+                    );
+                }
+            }
+        });
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,251 @@
+use crate::http_api::{DeviceParameters, GoveeApiClient};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Capability instances that `LocalScene::capture` records. Each entry
+/// pairs the instance name with the setter for the matching `DeviceCapture`
+/// field, so capture and restore stay in lock-step.
+const CAPTURED_INSTANCES: &[(&str, fn(&mut DeviceCapture, serde_json::Value))] = &[
+    ("powerSwitch", |c, v| c.power = Some(v)),
+    ("brightness", |c, v| c.brightness = Some(v)),
+    ("colorTemperatureK", |c, v| c.color_temperature_k = Some(v)),
+    ("colorRgb", |c, v| c.color_rgb = Some(v)),
+];
+
+/// The power/brightness/color/color-temperature state captured for a
+/// single device at the time a scene was saved. Fields are `None` when
+/// the device doesn't support that capability at all.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeviceCapture {
+    pub device_id: String,
+    pub power: Option<serde_json::Value>,
+    pub brightness: Option<serde_json::Value>,
+    pub color_temperature_k: Option<serde_json::Value>,
+    pub color_rgb: Option<serde_json::Value>,
+}
+
+/// A user-defined scene built from the live state of one or more devices,
+/// rather than one of the presets the Govee cloud offers.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LocalScene {
+    pub name: String,
+    pub captures: Vec<DeviceCapture>,
+}
+
+impl LocalScene {
+    /// Captures the current state of `device_ids` into a new named scene.
+    pub async fn capture(
+        name: &str,
+        client: &GoveeApiClient,
+        device_ids: &[String],
+    ) -> anyhow::Result<Self> {
+        let mut captures = vec![];
+
+        for device_id in device_ids {
+            let device = client.get_device_by_id(device_id).await?;
+            let state = client.get_device_state(&device).await?;
+
+            let mut capture = DeviceCapture {
+                device_id: device_id.clone(),
+                power: None,
+                brightness: None,
+                color_temperature_k: None,
+                color_rgb: None,
+            };
+
+            for (instance, setter) in CAPTURED_INSTANCES {
+                if let Some(value) = state.value_for(instance) {
+                    setter(&mut capture, value.clone());
+                }
+            }
+
+            captures.push(capture);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            captures,
+        })
+    }
+
+    /// Replays every captured value through `control_device`, re-clamping
+    /// each one against the target device's *current* capability ranges
+    /// in case firmware changed them since the scene was captured.
+    pub async fn restore(&self, client: &GoveeApiClient) -> anyhow::Result<()> {
+        for capture in &self.captures {
+            let device = client.get_device_by_id(&capture.device_id).await?;
+
+            if let Some(value) = &capture.power {
+                if let Some(cap) = device.capability_by_instance("powerSwitch") {
+                    client.control_device(&device, &cap, value.clone()).await?;
+                }
+            }
+
+            if let Some(value) = &capture.brightness {
+                if let Some(cap) = device.capability_by_instance("brightness") {
+                    let DeviceParameters::Integer { range, .. } = &cap.parameters else {
+                        anyhow::bail!("unexpected parameter type {:#?}", cap.parameters);
+                    };
+                    let clamped = Self::clamp_integer(range.min, range.max, value)?;
+                    client.control_device(&device, &cap, clamped).await?;
+                }
+            }
+
+            if let Some(value) = &capture.color_temperature_k {
+                if let Some(cap) = device.capability_by_instance("colorTemperatureK") {
+                    let DeviceParameters::Integer { range, .. } = &cap.parameters else {
+                        anyhow::bail!("unexpected parameter type {:#?}", cap.parameters);
+                    };
+                    let clamped = Self::clamp_integer(range.min, range.max, value)?;
+                    client.control_device(&device, &cap, clamped).await?;
+                }
+            }
+
+            if let Some(value) = &capture.color_rgb {
+                if let Some(cap) = device.capability_by_instance("colorRgb") {
+                    client.control_device(&device, &cap, value.clone()).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-clamps a captured integer value against the target device's
+    /// *current* `min..=max`, since firmware may have narrowed or widened
+    /// the range since the value was captured.
+    fn clamp_integer(min: u32, max: u32, value: &serde_json::Value) -> anyhow::Result<u32> {
+        let raw = value
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("captured value {value} is not an integer"))?
+            as u32;
+        Ok(raw.max(min).min(max))
+    }
+}
+
+/// Reads/writes the set of `LocalScene`s to a single JSON file on disk.
+pub struct SceneStore {
+    path: PathBuf,
+}
+
+impl SceneStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load_all(&self) -> anyhow::Result<Vec<LocalScene>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let data = std::fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    pub fn save_all(&self, scenes: &[LocalScene]) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(scenes)?)?;
+        Ok(())
+    }
+
+    /// Saves `scene`, replacing any existing scene of the same name.
+    pub fn upsert(&self, scene: LocalScene) -> anyhow::Result<()> {
+        let mut scenes = self.load_all()?;
+        scenes.retain(|s| s.name != scene.name);
+        scenes.push(scene);
+        self.save_all(&scenes)
+    }
+
+    pub fn get(&self, name: &str) -> anyhow::Result<Option<LocalScene>> {
+        Ok(self.load_all()?.into_iter().find(|s| s.name == name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn empty_capture(device_id: &str) -> DeviceCapture {
+        DeviceCapture {
+            device_id: device_id.to_string(),
+            power: None,
+            brightness: None,
+            color_temperature_k: None,
+            color_rgb: None,
+        }
+    }
+
+    fn scratch_store(name: &str) -> SceneStore {
+        let path = std::env::temp_dir().join(format!(
+            "govee2mqtt-scene-store-test-{name}-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        SceneStore::new(path)
+    }
+
+    #[test]
+    fn clamp_integer_passes_through_in_range_values() {
+        let value = serde_json::json!(50);
+        assert_eq!(LocalScene::clamp_integer(0, 100, &value).unwrap(), 50);
+    }
+
+    #[test]
+    fn clamp_integer_clamps_to_max() {
+        let value = serde_json::json!(9000);
+        assert_eq!(LocalScene::clamp_integer(2000, 6500, &value).unwrap(), 6500);
+    }
+
+    #[test]
+    fn clamp_integer_clamps_to_min() {
+        let value = serde_json::json!(10);
+        assert_eq!(LocalScene::clamp_integer(2000, 6500, &value).unwrap(), 2000);
+    }
+
+    #[test]
+    fn clamp_integer_rejects_non_integer_values() {
+        let value = serde_json::json!("not a number");
+        assert!(LocalScene::clamp_integer(0, 100, &value).is_err());
+    }
+
+    #[test]
+    fn store_load_all_is_empty_when_file_is_missing() {
+        let store = scratch_store("missing");
+        assert!(store.load_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn store_upsert_replaces_scene_with_same_name() {
+        let store = scratch_store("upsert");
+
+        store
+            .upsert(LocalScene {
+                name: "movie-night".to_string(),
+                captures: vec![empty_capture("device-1")],
+            })
+            .unwrap();
+        store
+            .upsert(LocalScene {
+                name: "movie-night".to_string(),
+                captures: vec![empty_capture("device-1"), empty_capture("device-2")],
+            })
+            .unwrap();
+
+        let scenes = store.load_all().unwrap();
+        assert_eq!(scenes.len(), 1);
+        assert_eq!(scenes[0].captures.len(), 2);
+
+        let fetched = store.get("movie-night").unwrap().unwrap();
+        assert_eq!(fetched.captures.len(), 2);
+        assert!(store.get("does-not-exist").unwrap().is_none());
+
+        std::fs::remove_file(
+            std::env::temp_dir().join(format!(
+                "govee2mqtt-scene-store-test-upsert-{}.json",
+                std::process::id()
+            )),
+        )
+        .ok();
+    }
+}
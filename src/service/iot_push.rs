@@ -0,0 +1,173 @@
+use crate::service::state::StateHandle;
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Maximum backoff between reconnect attempts to the Govee IoT endpoint.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How long the stream may stay quiet before we consider it dropped and
+/// fall back to polling over HTTP.
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A normalized device state change, broadcast to anyone interested
+/// (currently just the code that republishes to HASS) as it arrives from
+/// the IoT stream.
+#[derive(Clone, Debug)]
+pub struct DeviceStateUpdate {
+    pub device_id: String,
+    pub instance: String,
+    pub value: serde_json::Value,
+}
+
+/// Shape of the per-device state messages published on the account's IoT
+/// topic: one device/capability/value triple per message, where
+/// `capability` carries the instance name (e.g. `brightness`).
+#[derive(Deserialize, Debug)]
+struct IotStateMessage {
+    device: String,
+    capability: String,
+    value: serde_json::Value,
+}
+
+/// Subscribes to the Govee account IoT MQTT stream and feeds normalized
+/// device state into `state`, broadcasting each update so HASS entities
+/// can republish immediately instead of waiting for the next HTTP poll.
+///
+/// Runs until the process exits, reconnecting with backoff on failure and
+/// falling back to HTTP polling whenever the stream has been silent for
+/// longer than `STREAM_IDLE_TIMEOUT`.
+///
+/// This only defines the subsystem; starting it is the service startup
+/// code's job, same as the other long-running subsystems: construct one
+/// and `tokio::spawn(subsystem.run(...))` it alongside them.
+pub struct IotPushSubsystem {
+    updates: broadcast::Sender<DeviceStateUpdate>,
+}
+
+impl IotPushSubsystem {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(128);
+        Self { updates }
+    }
+
+    /// Subscribe to receive every update as it's ingested from the stream.
+    pub fn subscribe(&self) -> broadcast::Receiver<DeviceStateUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Runs forever, reconnecting with exponential backoff on every
+    /// error. `run_once` never returns `Ok` on its own (the only way out of
+    /// its inner loop is an error), so this loop never exits; that's
+    /// expected for a subsystem meant to run for the life of the process.
+    pub async fn run(
+        &self,
+        account_topic: &str,
+        mqtt_options: MqttOptions,
+        state: StateHandle,
+    ) -> anyhow::Result<()> {
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            if let Err(err) = self
+                .run_once(account_topic, mqtt_options.clone(), &state, &mut backoff)
+                .await
+            {
+                log::error!("govee IoT stream error, reconnecting in {backoff:?}: {err:#}");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    async fn run_once(
+        &self,
+        account_topic: &str,
+        mqtt_options: MqttOptions,
+        state: &StateHandle,
+        backoff: &mut Duration,
+    ) -> anyhow::Result<()> {
+        let (client, mut event_loop) = AsyncClient::new(mqtt_options, 32);
+        client.subscribe(account_topic, QoS::AtLeastOnce).await?;
+
+        // A successful connect + subscribe means the stream is healthy
+        // again: reset the backoff so a drop after hours of clean
+        // streaming doesn't inherit a stale, maxed-out delay.
+        *backoff = Duration::from_secs(1);
+
+        // While the stream is healthy, keep the HTTP poll fallback
+        // suppressed; as soon as we go quiet for too long, let it resume
+        // until the stream recovers.
+        let fallback = state.suppress_http_poll_fallback();
+
+        loop {
+            let event = tokio::time::timeout(STREAM_IDLE_TIMEOUT, event_loop.poll()).await;
+
+            let event = match event {
+                Ok(event) => event?,
+                Err(_elapsed) => {
+                    log::warn!("govee IoT stream idle for {STREAM_IDLE_TIMEOUT:?}, falling back to HTTP polling");
+                    drop(fallback);
+                    anyhow::bail!("stream idle timeout");
+                }
+            };
+
+            if let Event::Incoming(Packet::Publish(publish)) = event {
+                if let Err(err) = self.handle_message(&publish.payload, state).await {
+                    log::warn!("failed to process govee IoT message: {err:#}");
+                }
+            }
+        }
+    }
+
+    async fn handle_message(
+        &self,
+        payload: &[u8],
+        state: &StateHandle,
+    ) -> anyhow::Result<()> {
+        let message: IotStateMessage = serde_json::from_slice(payload)?;
+
+        state
+            .update_device_capability(&message.device, &message.capability, message.value.clone())
+            .await?;
+
+        // Best-effort: a lagging/inactive broadcast receiver shouldn't
+        // take down stream processing.
+        let _ = self.updates.send(DeviceStateUpdate {
+            device_id: message.device,
+            instance: message.capability,
+            value: message.value,
+        });
+
+        Ok(())
+    }
+}
+
+impl Default for IotPushSubsystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iot_state_message_parses_expected_shape() {
+        let message: IotStateMessage = serde_json::from_str(
+            r#"{"device": "AA:BB:CC:DD:EE:FF", "capability": "brightness", "value": 80}"#,
+        )
+        .unwrap();
+        assert_eq!(message.device, "AA:BB:CC:DD:EE:FF");
+        assert_eq!(message.capability, "brightness");
+        assert_eq!(message.value, serde_json::json!(80));
+    }
+
+    #[test]
+    fn iot_state_message_rejects_malformed_payload() {
+        let result: Result<IotStateMessage, _> = serde_json::from_str(r#"{"device": "x"}"#);
+        assert!(result.is_err());
+    }
+}